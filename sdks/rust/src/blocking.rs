@@ -0,0 +1,91 @@
+//! Synchronous mirror of the crate's async API, for callers (CLIs, scripts)
+//! that don't want to pull in an async runtime of their own.
+//!
+//! There is exactly one implementation of the request/token/discovery logic —
+//! [`crate::client::AuthVital`] — so it cannot drift between flavors. Each
+//! method here just drives that implementation to completion on a dedicated
+//! current-thread [`tokio::runtime::Runtime`], the same technique `reqwest`
+//! uses for its own `blocking` module.
+
+use crate::client::{AuthVitalBuilder as AsyncBuilder, AuthorizationRequest};
+use crate::credential::{BearerSource, Credential};
+use crate::error::Result;
+use crate::rt::block_on;
+use crate::token::TokenSet;
+
+/// Blocking counterpart of [`crate::AuthVital`]. Construct via [`AuthVital::builder`].
+#[derive(Clone)]
+pub struct AuthVital(crate::client::AuthVital);
+
+/// Blocking counterpart of [`crate::AuthVitalBuilder`]; same fields, `build()` doesn't require an async runtime.
+#[derive(Default)]
+pub struct AuthVitalBuilder(AsyncBuilder);
+
+impl AuthVitalBuilder {
+    pub fn issuer_url(mut self, url: impl Into<String>) -> Self {
+        self.0 = self.0.issuer_url(url);
+        self
+    }
+
+    pub fn client_id(mut self, id: impl Into<String>) -> Self {
+        self.0 = self.0.client_id(id);
+        self
+    }
+
+    pub fn client_secret(mut self, secret: impl Into<String>) -> Self {
+        self.0 = self.0.client_secret(secret);
+        self
+    }
+
+    pub fn redirect_uri(mut self, uri: impl Into<String>) -> Self {
+        self.0 = self.0.redirect_uri(uri);
+        self
+    }
+
+    pub fn refresh_skew(mut self, skew: std::time::Duration) -> Self {
+        self.0 = self.0.refresh_skew(skew);
+        self
+    }
+
+    pub fn build(self) -> Result<AuthVital> {
+        Ok(AuthVital(self.0.build()?))
+    }
+}
+
+impl AuthVital {
+    pub fn builder() -> AuthVitalBuilder {
+        AuthVitalBuilder::default()
+    }
+
+    pub fn authorization_url(&self, scopes: &[&str]) -> Result<AuthorizationRequest> {
+        block_on(self.0.authorization_url(scopes))
+    }
+
+    pub fn exchange_code(&self, code: &str, verifier: &str) -> Result<TokenSet> {
+        block_on(self.0.exchange_code(code, verifier))
+    }
+
+    pub fn refresh(&self, refresh_token: &str) -> Result<TokenSet> {
+        block_on(self.0.refresh(refresh_token))
+    }
+
+    pub fn access_token(&self) -> Result<String> {
+        block_on(self.0.access_token())
+    }
+
+    pub fn userinfo(&self) -> Result<serde_json::Value> {
+        block_on(self.0.userinfo())
+    }
+
+    /// Verifies a bearer token's signature (against this client's cached JWKS)
+    /// and standard claims, returning the [`Credential`] it carries.
+    pub fn verify_token(&self, token: &str) -> Result<Credential> {
+        block_on(self.0.verify_token(token))
+    }
+
+    /// Extracts and verifies the bearer token from a request-like value
+    /// (anything implementing [`BearerSource`], e.g. `http::Request<T>`).
+    pub fn credential_from_request(&self, request: &impl BearerSource) -> Result<Credential> {
+        block_on(self.0.credential_from_request(request))
+    }
+}
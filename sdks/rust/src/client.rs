@@ -0,0 +1,326 @@
+//! The `AuthVital` client: builder, authorization URL construction, and token exchange.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::credential::{BearerSource, Credential};
+use crate::discovery::DiscoveryCache;
+use crate::error::{AuthError, Result};
+use crate::pkce::{generate_state, Pkce};
+use crate::token::{TokenCache, TokenSet};
+use crate::transport::{AuthVitalTransport, HttpTransport, TokenResponse};
+
+/// Default skew applied to access token expiry: refresh this long before it actually expires.
+const DEFAULT_REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+/// An OIDC Authorization Code + PKCE client for a single AuthVital issuer.
+///
+/// Construct with [`AuthVital::builder`]. Clone is cheap; the client holds only
+/// `Arc`-wrapped state and is safe to share across tasks.
+#[derive(Clone)]
+pub struct AuthVital {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    client_id: String,
+    client_secret: Option<String>,
+    redirect_uri: String,
+    transport: Arc<dyn AuthVitalTransport>,
+    discovery: DiscoveryCache,
+    tokens: TokenCache,
+}
+
+/// Builds an [`AuthVital`] client.
+#[derive(Default)]
+pub struct AuthVitalBuilder {
+    issuer_url: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    redirect_uri: Option<String>,
+    refresh_skew: Option<Duration>,
+    transport: Option<Arc<dyn AuthVitalTransport>>,
+}
+
+impl AuthVitalBuilder {
+    pub fn issuer_url(mut self, url: impl Into<String>) -> Self {
+        self.issuer_url = Some(url.into());
+        self
+    }
+
+    pub fn client_id(mut self, id: impl Into<String>) -> Self {
+        self.client_id = Some(id.into());
+        self
+    }
+
+    /// Sets the client secret. Omit for public clients (e.g. native/mobile apps using PKCE alone).
+    pub fn client_secret(mut self, secret: impl Into<String>) -> Self {
+        self.client_secret = Some(secret.into());
+        self
+    }
+
+    pub fn redirect_uri(mut self, uri: impl Into<String>) -> Self {
+        self.redirect_uri = Some(uri.into());
+        self
+    }
+
+    /// How far ahead of actual expiry a token is considered due for refresh. Default 60s.
+    pub fn refresh_skew(mut self, skew: Duration) -> Self {
+        self.refresh_skew = Some(skew);
+        self
+    }
+
+    /// Injects a custom [`AuthVitalTransport`] in place of the real HTTP client.
+    ///
+    /// Intended for downstream tests that want to stub token exchange and
+    /// userinfo responses without a live identity server.
+    #[cfg(feature = "test-util")]
+    pub fn transport(mut self, transport: impl AuthVitalTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    pub fn build(self) -> Result<AuthVital> {
+        let issuer_url = self
+            .issuer_url
+            .ok_or_else(|| AuthError::Config("issuer_url is required".into()))?;
+        let client_id = self
+            .client_id
+            .ok_or_else(|| AuthError::Config("client_id is required".into()))?;
+        let redirect_uri = self
+            .redirect_uri
+            .ok_or_else(|| AuthError::Config("redirect_uri is required".into()))?;
+
+        let transport = self
+            .transport
+            .unwrap_or_else(|| Arc::new(HttpTransport::new()));
+        let discovery = DiscoveryCache::new(issuer_url, transport.clone());
+        let tokens = TokenCache::new(self.refresh_skew.unwrap_or(DEFAULT_REFRESH_SKEW));
+
+        Ok(AuthVital {
+            inner: Arc::new(Inner {
+                client_id,
+                client_secret: self.client_secret,
+                redirect_uri,
+                transport,
+                discovery,
+                tokens,
+            }),
+        })
+    }
+}
+
+/// The authorization URL plus the PKCE verifier and `state` the caller must
+/// persist (e.g. in a session cookie) until the redirect returns.
+pub struct AuthorizationRequest {
+    pub url: String,
+    pub pkce_verifier: String,
+    pub state: String,
+}
+
+impl From<TokenResponse> for TokenSet {
+    fn from(resp: TokenResponse) -> Self {
+        TokenSet {
+            access_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            id_token: resp.id_token,
+            scope: resp.scope,
+            expires_at: std::time::SystemTime::now(),
+        }
+        .with_expires_in(resp.expires_in)
+    }
+}
+
+impl AuthVital {
+    /// Starts building a client for a given issuer.
+    pub fn builder() -> AuthVitalBuilder {
+        AuthVitalBuilder::default()
+    }
+
+    /// Builds the authorization redirect URL for the Authorization Code + PKCE flow.
+    ///
+    /// `scopes` are space-joined into the `scope` parameter; `openid` is added
+    /// automatically if not already present.
+    pub async fn authorization_url(&self, scopes: &[&str]) -> Result<AuthorizationRequest> {
+        let document = self.inner.discovery.document().await?;
+        let pkce = Pkce::generate();
+        let state = generate_state();
+
+        let mut scope = scopes.to_vec();
+        if !scope.contains(&"openid") {
+            scope.insert(0, "openid");
+        }
+
+        let url = reqwest::Url::parse_with_params(
+            &document.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.inner.client_id),
+                ("redirect_uri", &self.inner.redirect_uri),
+                ("scope", &scope.join(" ")),
+                ("state", &state),
+                ("code_challenge", &pkce.challenge),
+                ("code_challenge_method", Pkce::METHOD),
+            ],
+        )
+        .map_err(|e| AuthError::Config(e.to_string()))?;
+
+        Ok(AuthorizationRequest {
+            url: url.into(),
+            pkce_verifier: pkce.verifier,
+            state,
+        })
+    }
+
+    /// Exchanges an authorization code for tokens, completing the flow.
+    ///
+    /// `verifier` must be the [`AuthorizationRequest::pkce_verifier`] returned
+    /// alongside the URL the caller originally redirected to.
+    pub async fn exchange_code(&self, code: &str, verifier: &str) -> Result<TokenSet> {
+        let mut params = HashMap::new();
+        params.insert("grant_type", "authorization_code");
+        params.insert("code", code);
+        params.insert("redirect_uri", &self.inner.redirect_uri);
+        params.insert("client_id", &self.inner.client_id);
+        params.insert("code_verifier", verifier);
+        self.request_token(params).await
+    }
+
+    /// Exchanges a refresh token for a new token set.
+    pub async fn refresh(&self, refresh_token: &str) -> Result<TokenSet> {
+        let mut params = HashMap::new();
+        params.insert("grant_type", "refresh_token");
+        params.insert("refresh_token", refresh_token);
+        params.insert("client_id", &self.inner.client_id);
+        self.request_token(params).await
+    }
+
+    /// Returns the cached access token, transparently refreshing it first if it's
+    /// within the configured skew of expiring and a refresh token is available.
+    pub async fn access_token(&self) -> Result<String> {
+        if let Some(tokens) = self.inner.tokens.get_fresh().await {
+            return Ok(tokens.access_token);
+        }
+
+        let current = self
+            .inner
+            .tokens
+            .get()
+            .await
+            .ok_or_else(|| AuthError::Config("no tokens; call exchange_code first".into()))?;
+        let refresh_token = match current.refresh_token {
+            Some(refresh_token) => refresh_token,
+            None if !current.needs_refresh(Duration::ZERO) => return Ok(current.access_token),
+            None => {
+                return Err(AuthError::InvalidToken(
+                    "access token expired, no refresh token".into(),
+                ))
+            }
+        };
+
+        let refreshed = self.refresh(&refresh_token).await?;
+        Ok(refreshed.access_token)
+    }
+
+    /// Fetches claims about the authenticated user from the issuer's userinfo endpoint.
+    pub async fn userinfo(&self) -> Result<serde_json::Value> {
+        let document = self.inner.discovery.document().await?;
+        let access_token = self.access_token().await?;
+        self.inner
+            .transport
+            .get(&document.userinfo_endpoint, Some(&access_token))
+            .await
+    }
+
+    /// Verifies a bearer token's signature (against this client's cached JWKS)
+    /// and standard claims, returning the [`Credential`] it carries.
+    pub async fn verify_token(&self, token: &str) -> Result<Credential> {
+        let document = self.inner.discovery.document().await?;
+        let jwks = self.inner.discovery.jwks().await?;
+        Credential::from_token(token, &jwks, &document.issuer, &self.inner.client_id)
+    }
+
+    /// Extracts and verifies the bearer token from a request-like value
+    /// (anything implementing [`BearerSource`], e.g. `http::Request<T>`).
+    pub async fn credential_from_request(&self, request: &impl BearerSource) -> Result<Credential> {
+        let token = request
+            .bearer_token()
+            .ok_or_else(|| AuthError::InvalidToken("no bearer token in request".into()))?;
+        self.verify_token(token).await
+    }
+
+    async fn request_token(&self, mut params: HashMap<&str, &str>) -> Result<TokenSet> {
+        let document = self.inner.discovery.document().await?;
+        if let Some(secret) = &self.inner.client_secret {
+            params.insert("client_secret", secret);
+        }
+
+        let token_response = self
+            .inner
+            .transport
+            .post_token(&document.token_endpoint, &params)
+            .await?;
+        let tokens: TokenSet = token_response.into();
+
+        self.inner.tokens.set(tokens.clone()).await;
+        Ok(tokens)
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    struct StubTransport;
+
+    #[async_trait]
+    impl AuthVitalTransport for StubTransport {
+        async fn get(&self, url: &str, _bearer_token: Option<&str>) -> Result<serde_json::Value> {
+            assert!(url.ends_with(".well-known/openid-configuration") || url.ends_with("/userinfo"));
+            Ok(json!({
+                "issuer": "https://id.example.com",
+                "authorization_endpoint": "https://id.example.com/authorize",
+                "token_endpoint": "https://id.example.com/token",
+                "userinfo_endpoint": "https://id.example.com/userinfo",
+                "jwks_uri": "https://id.example.com/jwks",
+                "sub": "user-1",
+            }))
+        }
+
+        async fn get_jwks(&self, _url: &str) -> Result<crate::discovery::Jwks> {
+            unimplemented!("not exercised by this test")
+        }
+
+        async fn post_token(
+            &self,
+            _url: &str,
+            _params: &HashMap<&str, &str>,
+        ) -> Result<TokenResponse> {
+            Ok(TokenResponse {
+                access_token: "at-1".into(),
+                refresh_token: Some("rt-1".into()),
+                id_token: None,
+                scope: Some("openid".into()),
+                expires_in: 3600,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn exchange_code_uses_injected_transport() {
+        let client = AuthVital::builder()
+            .issuer_url("https://id.example.com")
+            .client_id("client-1")
+            .redirect_uri("https://app.example.com/callback")
+            .transport(StubTransport)
+            .build()
+            .unwrap();
+
+        let tokens = client.exchange_code("code", "verifier").await.unwrap();
+        assert_eq!(tokens.access_token, "at-1");
+        assert_eq!(client.access_token().await.unwrap(), "at-1");
+    }
+}
@@ -0,0 +1,266 @@
+//! The authenticated principal extracted from a verified token, plus
+//! capability checks server middleware can consult before privileged operations.
+
+use std::collections::HashMap;
+
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde_json::{Map, Value};
+
+use crate::discovery::{Jwk, Jwks};
+use crate::error::{AuthError, Result};
+
+/// The authenticated principal extracted from a verified access or ID token.
+#[derive(Debug, Clone)]
+pub struct Credential {
+    pub subject: String,
+    pub issuer: String,
+    pub audience: Vec<String>,
+    pub scopes: Vec<String>,
+    /// All claims from the token, including ones not surfaced as dedicated fields.
+    pub claims: Map<String, Value>,
+    /// The AuthVital instance this credential was verified against. Equal to
+    /// `issuer` for single-issuer clients; set independently by
+    /// [`crate::federation::IssuerRegistry::verify_federated`] so callers can
+    /// tell which federated peer a request originated from even after the
+    /// credential is normalized.
+    pub origin_instance: String,
+}
+
+impl Credential {
+    /// Whether the token carried `scope` in its space-delimited `scope` claim.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+
+    /// Returns a claim by name, if present.
+    pub fn claim(&self, name: &str) -> Option<&Value> {
+        self.claims.get(name)
+    }
+
+    /// Checks `permission`, returning [`AuthError::Forbidden`] if it isn't held.
+    pub fn require(&self, permission: Permission) -> Result<()> {
+        if permission.is_satisfied_by(self) {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden(format!(
+                "subject {} lacks {permission}",
+                self.subject
+            )))
+        }
+    }
+
+    /// Verifies `token`'s signature against `jwks` and its standard claims
+    /// (`exp`, `nbf`, `aud`, `iss`), then extracts a [`Credential`] from it.
+    pub fn from_token(
+        token: &str,
+        jwks: &Jwks,
+        expected_issuer: &str,
+        expected_audience: &str,
+    ) -> Result<Self> {
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        let jwk = jwks
+            .find(header.kid.as_deref())
+            .ok_or_else(|| AuthError::InvalidToken("no matching key in JWKS".into()))?;
+        let decoding_key = decoding_key_for(jwk)?;
+
+        // The algorithm is pinned from the resolved JWK, never from the
+        // token's own (attacker-controlled) header -- otherwise a token
+        // could pick e.g. HS256 over a key meant only for RS256 verification.
+        let algorithm = algorithm_for(jwk)?;
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[expected_issuer]);
+        validation.set_audience(&[expected_audience]);
+        validation.validate_nbf = true;
+
+        let data = jsonwebtoken::decode::<Map<String, Value>>(token, &decoding_key, &validation)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        Self::from_claims(data.claims)
+    }
+
+    fn from_claims(claims: Map<String, Value>) -> Result<Self> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AuthError::InvalidToken("missing sub claim".into()))?
+            .to_string();
+        let issuer = claims
+            .get("iss")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AuthError::InvalidToken("missing iss claim".into()))?
+            .to_string();
+        let audience = match claims.get("aud") {
+            Some(Value::String(s)) => vec![s.clone()],
+            Some(Value::Array(values)) => values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let scopes = claims
+            .get("scope")
+            .and_then(Value::as_str)
+            .map(|s| s.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Ok(Credential {
+            subject,
+            origin_instance: issuer.clone(),
+            issuer,
+            audience,
+            scopes,
+            claims,
+        })
+    }
+}
+
+/// Pins the signing algorithm from the resolved JWK's own metadata, never
+/// from the token header: trusting the token's `alg` lets an attacker pick
+/// whichever algorithm is weakest for a given key (the classic JWT
+/// algorithm-confusion attack).
+fn algorithm_for(jwk: &Jwk) -> Result<Algorithm> {
+    if let Some(alg) = jwk.alg.as_deref() {
+        return alg
+            .parse()
+            .map_err(|_| AuthError::InvalidToken(format!("unsupported JWK alg {alg}")));
+    }
+    match jwk.kty.as_str() {
+        "RSA" => Ok(Algorithm::RS256),
+        "EC" => match jwk.crv.as_deref() {
+            Some("P-256") => Ok(Algorithm::ES256),
+            Some("P-384") => Ok(Algorithm::ES384),
+            other => Err(AuthError::InvalidToken(format!(
+                "unsupported EC curve {}",
+                other.unwrap_or("<none>")
+            ))),
+        },
+        other => Err(AuthError::InvalidToken(format!("unsupported key type {other}"))),
+    }
+}
+
+fn decoding_key_for(jwk: &Jwk) -> Result<DecodingKey> {
+    match jwk.kty.as_str() {
+        "RSA" => {
+            let n = jwk
+                .n
+                .as_deref()
+                .ok_or_else(|| AuthError::InvalidToken("RSA key missing n".into()))?;
+            let e = jwk
+                .e
+                .as_deref()
+                .ok_or_else(|| AuthError::InvalidToken("RSA key missing e".into()))?;
+            DecodingKey::from_rsa_components(n, e).map_err(|e| AuthError::InvalidToken(e.to_string()))
+        }
+        "EC" => {
+            let x = jwk
+                .x
+                .as_deref()
+                .ok_or_else(|| AuthError::InvalidToken("EC key missing x".into()))?;
+            let y = jwk
+                .y
+                .as_deref()
+                .ok_or_else(|| AuthError::InvalidToken("EC key missing y".into()))?;
+            DecodingKey::from_ec_components(x, y).map_err(|e| AuthError::InvalidToken(e.to_string()))
+        }
+        other => Err(AuthError::InvalidToken(format!("unsupported key type {other}"))),
+    }
+}
+
+/// A capability a [`Credential`] must hold, checked by [`Credential::require`].
+#[derive(Debug, Clone)]
+pub enum Permission {
+    /// The credential's `scope` claim must contain this scope verbatim.
+    Scope(&'static str),
+}
+
+impl Permission {
+    fn is_satisfied_by(&self, cred: &Credential) -> bool {
+        match self {
+            Permission::Scope(scope) => cred.has_scope(scope),
+        }
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Permission::Scope(scope) => write!(f, "scope `{scope}`"),
+        }
+    }
+}
+
+/// Pulls a bearer token out of a request-like type, so [`Credential`] can be
+/// extracted directly from whatever HTTP request type a caller's framework uses.
+pub trait BearerSource {
+    fn bearer_token(&self) -> Option<&str>;
+}
+
+impl BearerSource for HashMap<String, String> {
+    /// Looks the token up under an `authorization` key, matched
+    /// case-insensitively since HTTP header names are case-insensitive.
+    fn bearer_token(&self) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("authorization"))
+            .and_then(|(_, value)| value.strip_prefix("Bearer "))
+    }
+}
+
+impl<T> BearerSource for http::Request<T> {
+    fn bearer_token(&self) -> Option<&str> {
+        self.headers()
+            .get(http::header::AUTHORIZATION)?
+            .to_str()
+            .ok()?
+            .strip_prefix("Bearer ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn claims(extra: Value) -> Map<String, Value> {
+        let mut map = Map::new();
+        map.insert("sub".into(), json!("user-1"));
+        map.insert("iss".into(), json!("https://id.example.com"));
+        map.insert("aud".into(), json!("client-1"));
+        map.insert("scope".into(), json!("files:read files:write"));
+        if let Value::Object(extra) = extra {
+            map.extend(extra);
+        }
+        map
+    }
+
+    #[test]
+    fn has_scope_checks_space_delimited_claim() {
+        let cred = Credential::from_claims(claims(json!({}))).unwrap();
+        assert!(cred.has_scope("files:write"));
+        assert!(!cred.has_scope("files:delete"));
+    }
+
+    #[test]
+    fn require_returns_forbidden_when_scope_missing() {
+        let cred = Credential::from_claims(claims(json!({}))).unwrap();
+        assert!(cred.require(Permission::Scope("files:write")).is_ok());
+        assert!(matches!(
+            cred.require(Permission::Scope("files:delete")),
+            Err(AuthError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn bearer_token_parses_authorization_header() {
+        let mut headers = HashMap::new();
+        headers.insert("authorization".to_string(), "Bearer abc.def.ghi".to_string());
+        assert_eq!(headers.bearer_token(), Some("abc.def.ghi"));
+    }
+
+    #[test]
+    fn bearer_token_matches_header_key_case_insensitively() {
+        let mut headers = HashMap::new();
+        headers.insert("AUTHORIZATION".to_string(), "Bearer abc.def.ghi".to_string());
+        assert_eq!(headers.bearer_token(), Some("abc.def.ghi"));
+    }
+}
@@ -0,0 +1,121 @@
+//! OIDC discovery document and JWKS fetching, with in-memory caching.
+
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::{AuthError, Result};
+use crate::transport::AuthVitalTransport;
+
+/// The subset of the `.well-known/openid-configuration` document this SDK relies on.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscoveryDocument {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// A single JSON Web Key as returned by an issuer's `jwks_uri`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Jwk {
+    pub kid: Option<String>,
+    pub kty: String,
+    pub alg: Option<String>,
+    #[serde(rename = "use")]
+    pub usage: Option<String>,
+    pub n: Option<String>,
+    pub e: Option<String>,
+    pub x: Option<String>,
+    pub y: Option<String>,
+    pub crv: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    /// Finds the key matching a JWT's `kid` header, falling back to the sole key
+    /// if the set contains exactly one and the token omitted `kid`.
+    pub fn find(&self, kid: Option<&str>) -> Option<&Jwk> {
+        match kid {
+            Some(kid) => self.keys.iter().find(|k| k.kid.as_deref() == Some(kid)),
+            None if self.keys.len() == 1 => self.keys.first(),
+            None => None,
+        }
+    }
+}
+
+/// How long a cached discovery document or JWKS is trusted before being refetched.
+const CACHE_TTL: Duration = Duration::from_secs(3600);
+
+struct Cached<T> {
+    value: T,
+    fetched_at: Instant,
+}
+
+/// Fetches and caches an issuer's discovery document and JWKS.
+///
+/// One `DiscoveryCache` is owned per [`AuthVital`](crate::AuthVital) client and
+/// shared across token operations so repeated calls don't refetch on every request.
+pub struct DiscoveryCache {
+    issuer_url: String,
+    transport: Arc<dyn AuthVitalTransport>,
+    document: tokio::sync::RwLock<Option<Cached<DiscoveryDocument>>>,
+    jwks: tokio::sync::RwLock<Option<Cached<Jwks>>>,
+}
+
+impl DiscoveryCache {
+    pub fn new(issuer_url: String, transport: Arc<dyn AuthVitalTransport>) -> Self {
+        Self {
+            issuer_url,
+            transport,
+            document: tokio::sync::RwLock::new(None),
+            jwks: tokio::sync::RwLock::new(None),
+        }
+    }
+
+    /// Returns the cached discovery document, fetching it on first use or after expiry.
+    pub async fn document(&self) -> Result<DiscoveryDocument> {
+        if let Some(cached) = self.document.read().await.as_ref() {
+            if cached.fetched_at.elapsed() < CACHE_TTL {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.issuer_url.trim_end_matches('/')
+        );
+        let raw = self.transport.get(&url, None).await?;
+        let document: DiscoveryDocument =
+            serde_json::from_value(raw).map_err(|e| AuthError::Discovery(e.to_string()))?;
+
+        *self.document.write().await = Some(Cached {
+            value: document.clone(),
+            fetched_at: Instant::now(),
+        });
+        Ok(document)
+    }
+
+    /// Returns the cached JWKS, fetching it on first use or after expiry.
+    pub async fn jwks(&self) -> Result<Jwks> {
+        if let Some(cached) = self.jwks.read().await.as_ref() {
+            if cached.fetched_at.elapsed() < CACHE_TTL {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let document = self.document().await?;
+        let jwks = self.transport.get_jwks(&document.jwks_uri).await?;
+
+        *self.jwks.write().await = Some(Cached {
+            value: jwks.clone(),
+            fetched_at: Instant::now(),
+        });
+        Ok(jwks)
+    }
+}
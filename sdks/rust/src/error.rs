@@ -0,0 +1,41 @@
+//! Error types returned by the AuthVital SDK.
+
+use std::fmt;
+
+/// The error type for all fallible operations in this crate.
+#[derive(Debug)]
+pub enum AuthError {
+    /// The OIDC discovery document could not be parsed. Fetch failures, and
+    /// JWKS fetch or decode failures, are reported as `Transport` or `Provider`.
+    Discovery(String),
+    /// The identity provider returned an error response (e.g. token exchange failed).
+    Provider { status: u16, body: String },
+    /// A token failed signature, claim, or expiry validation.
+    InvalidToken(String),
+    /// The underlying HTTP transport failed.
+    Transport(String),
+    /// A caller attempted an operation without the required scope or permission.
+    Forbidden(String),
+    /// Request or builder configuration was incomplete or inconsistent.
+    Config(String),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Discovery(msg) => write!(f, "OIDC discovery failed: {msg}"),
+            AuthError::Provider { status, body } => {
+                write!(f, "identity provider returned {status}: {body}")
+            }
+            AuthError::InvalidToken(msg) => write!(f, "invalid token: {msg}"),
+            AuthError::Transport(msg) => write!(f, "transport error: {msg}"),
+            AuthError::Forbidden(msg) => write!(f, "forbidden: {msg}"),
+            AuthError::Config(msg) => write!(f, "invalid configuration: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Convenience alias used throughout the crate.
+pub type Result<T> = std::result::Result<T, AuthError>;
@@ -0,0 +1,218 @@
+//! Multi-issuer credential verification, so a single service can trust users
+//! originating from several independently-run AuthVital instances — the same
+//! relationship federated social instances have with one another.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::credential::Credential;
+use crate::discovery::DiscoveryCache;
+use crate::error::{AuthError, Result};
+use crate::transport::AuthVitalTransport;
+
+/// Which issuers an [`IssuerRegistry`] is willing to verify tokens from.
+pub enum TrustPolicy {
+    /// Only these issuer URLs are ever trusted; every other `iss` is rejected
+    /// before a single request is made to it.
+    Allowlist(Vec<String>),
+    /// Any issuer is accepted, with its discovery document and JWKS fetched
+    /// (and pinned in the registry) the first time a token from it is seen.
+    ///
+    /// The issuer URL comes straight off the submitted token's unverified
+    /// `iss` claim, so this policy is only safe when every token submitter is
+    /// already trusted not to name an internal/loopback URL (`localhost` and
+    /// internal IP literals are still rejected outright, but domain names are
+    /// not resolved before the request, and a malicious submitter could use this
+    /// as an SSRF probe against arbitrary public hosts). Pair with an
+    /// allowlist at a network boundary (e.g. an egress proxy) if submitters
+    /// aren't fully trusted.
+    DynamicDiscovery,
+}
+
+/// Maps issuer URLs to their cached discovery document + JWKS, and verifies
+/// incoming tokens against whichever issuer they claim to be from.
+///
+/// Construct one per service (not per request) and reuse it; each issuer's
+/// discovery/JWKS cache is shared across every [`verify_federated`](Self::verify_federated) call.
+pub struct IssuerRegistry {
+    transport: Arc<dyn AuthVitalTransport>,
+    policy: TrustPolicy,
+    audience: String,
+    issuers: tokio::sync::RwLock<HashMap<String, DiscoveryCache>>,
+}
+
+impl IssuerRegistry {
+    /// `audience` is the `client_id`/`aud` this service expects tokens to have been issued for.
+    pub fn new(transport: Arc<dyn AuthVitalTransport>, policy: TrustPolicy, audience: String) -> Self {
+        Self {
+            transport,
+            policy,
+            audience,
+            issuers: tokio::sync::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Verifies `token` against whichever issuer its `iss` claim names,
+    /// selecting and trusting that issuer per this registry's [`TrustPolicy`].
+    pub async fn verify_federated(&self, token: &str) -> Result<Credential> {
+        let issuer = peek_issuer(token)?;
+        validate_issuer_url(&issuer)?;
+        self.ensure_trusted(&issuer)?;
+        self.ensure_cached(&issuer).await;
+
+        let (document, jwks) = {
+            let issuers = self.issuers.read().await;
+            let cache = issuers
+                .get(&issuer)
+                .expect("ensure_cached just inserted this issuer");
+            (cache.document().await?, cache.jwks().await?)
+        };
+
+        let mut credential =
+            Credential::from_token(token, &jwks, &document.issuer, &self.audience)?;
+        credential.origin_instance = issuer;
+        Ok(credential)
+    }
+
+    fn ensure_trusted(&self, issuer: &str) -> Result<()> {
+        match &self.policy {
+            TrustPolicy::Allowlist(allowed) => {
+                if allowed.iter().any(|a| a == issuer) {
+                    Ok(())
+                } else {
+                    Err(AuthError::Forbidden(format!("issuer {issuer} is not allowlisted")))
+                }
+            }
+            TrustPolicy::DynamicDiscovery => Ok(()),
+        }
+    }
+
+    async fn ensure_cached(&self, issuer: &str) {
+        if self.issuers.read().await.contains_key(issuer) {
+            return;
+        }
+        let mut issuers = self.issuers.write().await;
+        issuers
+            .entry(issuer.to_string())
+            .or_insert_with(|| DiscoveryCache::new(issuer.to_string(), self.transport.clone()));
+    }
+}
+
+/// Rejects issuer URLs that would turn discovery-document fetching into an
+/// SSRF primitive: the `iss` claim is unverified attacker input at this
+/// point, so before it's ever used to make a request it must be `https`, must
+/// not be `localhost`, and must not be a loopback, private, link-local, or
+/// otherwise internal IP literal. Domain names are not resolved here, so a
+/// name whose DNS record points at an internal address is accepted.
+fn validate_issuer_url(issuer: &str) -> Result<()> {
+    let parsed = url::Url::parse(issuer)
+        .map_err(|e| AuthError::InvalidToken(format!("invalid issuer URL: {e}")))?;
+
+    if parsed.scheme() != "https" {
+        return Err(AuthError::InvalidToken("issuer URL must use https".into()));
+    }
+
+    match parsed
+        .host()
+        .ok_or_else(|| AuthError::InvalidToken("issuer URL has no host".into()))?
+    {
+        url::Host::Domain(domain) if domain.eq_ignore_ascii_case("localhost") => {
+            Err(AuthError::InvalidToken("issuer URL must not be localhost".into()))
+        }
+        url::Host::Domain(_) => Ok(()),
+        url::Host::Ipv4(ip) if is_internal_ip(&std::net::IpAddr::V4(ip)) => Err(
+            AuthError::InvalidToken("issuer URL must not resolve to an internal address".into()),
+        ),
+        url::Host::Ipv6(ip) if is_internal_ip(&std::net::IpAddr::V6(ip)) => Err(
+            AuthError::InvalidToken("issuer URL must not resolve to an internal address".into()),
+        ),
+        url::Host::Ipv4(_) | url::Host::Ipv6(_) => Ok(()),
+    }
+}
+
+fn is_internal_ip(ip: &std::net::IpAddr) -> bool {
+    match ip {
+        std::net::IpAddr::V4(v4) => {
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+        }
+        std::net::IpAddr::V6(v6) => {
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (v6.segments()[0] & 0xfe00) == 0xfc00
+                || (v6.segments()[0] & 0xffc0) == 0xfe80
+                || v6
+                    .to_ipv4_mapped()
+                    .map_or(false, |v4| is_internal_ip(&std::net::IpAddr::V4(v4)))
+        }
+    }
+}
+
+/// Reads the `iss` claim out of a JWT without verifying its signature, so the
+/// registry knows which issuer's keys to fetch before it can check anything.
+/// The claim is re-validated against the issuer's own cached `iss` after
+/// signature verification, so a forged `iss` here is harmless on its own.
+fn peek_issuer(token: &str) -> Result<String> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| AuthError::InvalidToken("malformed JWT".into()))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+    let claims: serde_json::Value =
+        serde_json::from_slice(&bytes).map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+    claims
+        .get("iss")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| AuthError::InvalidToken("missing iss claim".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_with_claims(claims: serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        format!("{header}.{payload}.unsigned")
+    }
+
+    #[test]
+    fn peek_issuer_reads_unverified_claim() {
+        let token = token_with_claims(serde_json::json!({ "iss": "https://peer.example.com" }));
+        assert_eq!(peek_issuer(&token).unwrap(), "https://peer.example.com");
+    }
+
+    #[test]
+    fn peek_issuer_rejects_missing_claim() {
+        let token = token_with_claims(serde_json::json!({ "sub": "user-1" }));
+        assert!(peek_issuer(&token).is_err());
+    }
+
+    #[test]
+    fn validate_issuer_url_accepts_public_https() {
+        assert!(validate_issuer_url("https://id.example.com").is_ok());
+    }
+
+    #[test]
+    fn validate_issuer_url_rejects_non_https() {
+        assert!(validate_issuer_url("http://id.example.com").is_err());
+    }
+
+    #[test]
+    fn validate_issuer_url_rejects_loopback_and_private_hosts() {
+        assert!(validate_issuer_url("https://localhost").is_err());
+        assert!(validate_issuer_url("https://127.0.0.1").is_err());
+        assert!(validate_issuer_url("https://169.254.169.254").is_err());
+        assert!(validate_issuer_url("https://10.0.0.5").is_err());
+        assert!(validate_issuer_url("https://[::1]").is_err());
+    }
+}
@@ -2,36 +2,47 @@
 //!
 //! Official Rust SDK for AuthVital Identity Platform.
 //!
-//! **Status: Coming Soon**
+//! Implements the OpenID Connect Authorization Code flow with PKCE:
 //!
-//! This crate is a placeholder. The full SDK is under active development.
-//! Follow <https://github.com/authvital/authvital> for updates!
-
-#![doc = include_str!("../README.md")]
-
-/// Placeholder for the AuthVital client.
-/// 
-/// # Coming Soon
-/// 
-/// This SDK is under active development.
-pub struct AuthVital;
+//! ```no_run
+//! # async fn run() -> authvital::Result<()> {
+//! let client = authvital::AuthVital::builder()
+//!     .issuer_url("https://id.example.com")
+//!     .client_id("my-client")
+//!     .redirect_uri("https://app.example.com/callback")
+//!     .build()?;
+//!
+//! let req = client.authorization_url(&["profile", "email"]).await?;
+//! // redirect the user to `req.url`, persisting `req.pkce_verifier` and `req.state`
+//!
+//! let tokens = client.exchange_code("code-from-redirect", &req.pkce_verifier).await?;
+//! let claims = client.userinfo().await?;
+//! # let _ = tokens;
+//! # let _ = claims;
+//! # Ok(())
+//! # }
+//! ```
 
-impl AuthVital {
-    /// Creates a new AuthVital client.
-    /// 
-    /// # Panics
-    /// 
-    /// This is a placeholder and will panic. The full SDK is coming soon!
-    pub fn new() -> Self {
-        unimplemented!("AuthVital Rust SDK is coming soon! Follow https://github.com/authvital/authvital for updates.")
-    }
-}
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod client;
+pub mod credential;
+mod discovery;
+mod error;
+pub mod federation;
+pub mod macaroon;
+mod pkce;
+#[cfg(feature = "blocking")]
+mod rt;
+pub mod store;
+mod token;
+pub mod transport;
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn placeholder() {
-        // Placeholder test
-        assert!(true);
-    }
-}
+pub use client::{AuthVital, AuthVitalBuilder, AuthorizationRequest};
+pub use credential::{BearerSource, Credential, Permission};
+pub use discovery::{DiscoveryDocument, Jwk, Jwks};
+pub use error::{AuthError, Result};
+pub use federation::{IssuerRegistry, TrustPolicy};
+pub use pkce::Pkce;
+pub use token::TokenSet;
+pub use transport::AuthVitalTransport;
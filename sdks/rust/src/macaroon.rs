@@ -0,0 +1,460 @@
+//! Macaroon-based delegated tokens: bearer credentials with attenuating,
+//! offline-verifiable caveats.
+//!
+//! A macaroon is an identifier plus an HMAC chain over an ordered list of
+//! caveats: `sig0 = HMAC(root_key, identifier)`, and for each appended
+//! first-party caveat `c_i`, `sig_i = HMAC(sig_{i-1}, c_i)`. Anyone holding a
+//! macaroon can append caveats (further restricting it) without contacting the
+//! issuer, but cannot remove existing ones without invalidating the signature.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+use crate::error::{AuthError, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// A first-party caveat predicate (e.g. `"expires < 2026-01-01T00:00:00Z"`,
+/// `"scope = read"`), or a third-party caveat reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaveatKind {
+    /// A predicate the verifier checks directly against request context.
+    FirstParty(String),
+    /// A reference to a caveat that must be discharged by a third party.
+    ThirdParty {
+        location: String,
+        verification_key_id: Vec<u8>,
+        caveat_id: Vec<u8>,
+    },
+}
+
+/// Registers how a first-party caveat predicate string is checked at verification time.
+///
+/// Callers implement this to decide which predicate syntaxes they accept; the
+/// crate itself ships no built-in predicate parser.
+pub trait Caveat {
+    /// Returns `true` if `predicate` holds given the current verification context.
+    fn check(&self, predicate: &str) -> bool;
+}
+
+/// A signed, appendable bearer token.
+#[derive(Debug, Clone)]
+pub struct Macaroon {
+    pub identifier: Vec<u8>,
+    /// Identifies which root key in the [`Confectionary`]'s keyring signed this macaroon.
+    pub key_id: String,
+    pub caveats: Vec<CaveatKind>,
+    signature: [u8; 32],
+}
+
+impl Macaroon {
+    /// Appends a first-party caveat, extending the HMAC chain.
+    #[must_use]
+    pub fn with_caveat(mut self, predicate: impl Into<String>) -> Self {
+        let predicate = predicate.into();
+        self.signature = hmac(&self.signature, predicate.as_bytes());
+        self.caveats.push(CaveatKind::FirstParty(predicate));
+        self
+    }
+
+    /// Appends a third-party caveat. The holder must later obtain a discharge
+    /// macaroon from `location` and present it alongside this one.
+    #[must_use]
+    pub fn with_third_party_caveat(
+        mut self,
+        location: impl Into<String>,
+        verification_key_id: Vec<u8>,
+        caveat_id: Vec<u8>,
+    ) -> Self {
+        let mut data = verification_key_id.clone();
+        data.extend_from_slice(&caveat_id);
+        self.signature = hmac(&self.signature, &data);
+        self.caveats.push(CaveatKind::ThirdParty {
+            location: location.into(),
+            verification_key_id,
+            caveat_id,
+        });
+        self
+    }
+
+    /// Binds a discharge macaroon to this root macaroon so it can only be used
+    /// alongside it: `bind(root_sig, discharge_sig) = HMAC(zeros, root_sig || discharge_sig)`.
+    pub fn bind_discharge(&self, discharge: &Macaroon) -> Macaroon {
+        let mut data = self.signature.to_vec();
+        data.extend_from_slice(&discharge.signature);
+        let bound_sig = hmac(&[0u8; 32], &data);
+        Macaroon {
+            signature: bound_sig,
+            ..discharge.clone()
+        }
+    }
+
+    /// Serializes to this crate's own base64url format: `key_id.identifier.caveats.signature`,
+    /// each component base64url-encoded. This is not the libmacaroons packet
+    /// format; the result is read back only by [`Macaroon::deserialize`].
+    pub fn serialize(&self) -> String {
+        let caveats = self
+            .caveats
+            .iter()
+            .map(encode_caveat)
+            .collect::<Vec<_>>()
+            .join(":");
+        format!(
+            "{}.{}.{}.{}",
+            URL_SAFE_NO_PAD.encode(self.key_id.as_bytes()),
+            URL_SAFE_NO_PAD.encode(&self.identifier),
+            URL_SAFE_NO_PAD.encode(caveats),
+            URL_SAFE_NO_PAD.encode(self.signature),
+        )
+    }
+
+    /// Parses a token produced by [`Macaroon::serialize`].
+    pub fn deserialize(token: &str) -> Result<Self> {
+        let mut parts = token.split('.');
+        let (key_id, identifier, caveats, signature) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        );
+        if parts.next().is_some() {
+            return Err(AuthError::InvalidToken("malformed macaroon packet".into()));
+        }
+        let decode = |s: Option<&str>| -> Result<Vec<u8>> {
+            URL_SAFE_NO_PAD
+                .decode(s.ok_or_else(|| AuthError::InvalidToken("malformed macaroon packet".into()))?)
+                .map_err(|e| AuthError::InvalidToken(e.to_string()))
+        };
+
+        let key_id = String::from_utf8(decode(key_id)?)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        let identifier = decode(identifier)?;
+        let caveats_raw =
+            String::from_utf8(decode(caveats)?).map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        let caveats = if caveats_raw.is_empty() {
+            Vec::new()
+        } else {
+            caveats_raw
+                .split(':')
+                .map(decode_caveat)
+                .collect::<Result<Vec<_>>>()?
+        };
+        let signature_bytes = decode(signature)?;
+        let signature: [u8; 32] = signature_bytes
+            .try_into()
+            .map_err(|_| AuthError::InvalidToken("signature must be 32 bytes".into()))?;
+
+        Ok(Macaroon {
+            identifier,
+            key_id,
+            caveats,
+            signature,
+        })
+    }
+}
+
+fn encode_caveat(c: &CaveatKind) -> String {
+    match c {
+        CaveatKind::FirstParty(predicate) => {
+            format!("0{}", URL_SAFE_NO_PAD.encode(predicate))
+        }
+        CaveatKind::ThirdParty {
+            location,
+            verification_key_id,
+            caveat_id,
+        } => format!(
+            "1{}|{}|{}",
+            URL_SAFE_NO_PAD.encode(location),
+            URL_SAFE_NO_PAD.encode(verification_key_id),
+            URL_SAFE_NO_PAD.encode(caveat_id),
+        ),
+    }
+}
+
+fn decode_caveat(s: &str) -> Result<CaveatKind> {
+    let err = || AuthError::InvalidToken("malformed caveat packet".into());
+    let tag = s.get(..1).ok_or_else(err)?;
+    let rest = s.get(1..).ok_or_else(err)?;
+    match tag {
+        "0" => {
+            let predicate = String::from_utf8(
+                URL_SAFE_NO_PAD.decode(rest).map_err(|_| err())?,
+            )
+            .map_err(|_| err())?;
+            Ok(CaveatKind::FirstParty(predicate))
+        }
+        "1" => {
+            let mut fields = rest.split('|');
+            let location = String::from_utf8(
+                URL_SAFE_NO_PAD
+                    .decode(fields.next().ok_or_else(err)?)
+                    .map_err(|_| err())?,
+            )
+            .map_err(|_| err())?;
+            let verification_key_id = URL_SAFE_NO_PAD
+                .decode(fields.next().ok_or_else(err)?)
+                .map_err(|_| err())?;
+            let caveat_id = URL_SAFE_NO_PAD
+                .decode(fields.next().ok_or_else(err)?)
+                .map_err(|_| err())?;
+            Ok(CaveatKind::ThirdParty {
+                location,
+                verification_key_id,
+                caveat_id,
+            })
+        }
+        _ => Err(err()),
+    }
+}
+
+/// Holds the root keys used to mint and verify macaroons, keyed by `key_id`
+/// so old tokens keep verifying across key rotation.
+#[derive(Default)]
+pub struct Confectionary {
+    keys: std::collections::HashMap<String, Vec<u8>>,
+}
+
+impl Confectionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a root key under `key_id`, used for both minting and verification.
+    pub fn add_key(&mut self, key_id: impl Into<String>, root_key: Vec<u8>) {
+        self.keys.insert(key_id.into(), root_key);
+    }
+
+    /// Mints a new macaroon with no caveats, signed under `key_id`'s root key.
+    pub fn mint(&self, key_id: &str, identifier: Vec<u8>) -> Result<Macaroon> {
+        let root_key = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| AuthError::Config(format!("unknown macaroon key id {key_id}")))?;
+        Ok(Macaroon {
+            signature: hmac(root_key, &identifier),
+            identifier,
+            key_id: key_id.to_string(),
+            caveats: Vec::new(),
+        })
+    }
+
+    /// Recomputes the HMAC chain from the root key, checks every first-party
+    /// caveat against `verifier`, and discharges every third-party caveat
+    /// against a matching macaroon in `discharges` (matched by `caveat_id`
+    /// against the discharge's `identifier`, then checked against its
+    /// `bind_discharge` binding). A third-party caveat with no matching,
+    /// correctly-bound discharge fails verification.
+    pub fn verify(&self, macaroon: &Macaroon, discharges: &[Macaroon], verifier: &impl Caveat) -> Result<()> {
+        let root_key = self
+            .keys
+            .get(&macaroon.key_id)
+            .ok_or_else(|| AuthError::InvalidToken(format!("unknown macaroon key id {}", macaroon.key_id)))?;
+
+        let sig = chain_signature(root_key, &macaroon.identifier, &macaroon.caveats);
+        if !bool::from(sig.ct_eq(&macaroon.signature)) {
+            return Err(AuthError::InvalidToken("signature mismatch".into()));
+        }
+
+        for caveat in &macaroon.caveats {
+            match caveat {
+                CaveatKind::FirstParty(predicate) => {
+                    if !verifier.check(predicate) {
+                        return Err(AuthError::InvalidToken(format!(
+                            "caveat not satisfied: {predicate}"
+                        )));
+                    }
+                }
+                CaveatKind::ThirdParty {
+                    verification_key_id,
+                    caveat_id,
+                    ..
+                } => {
+                    verify_discharge(macaroon.signature, verification_key_id, caveat_id, discharges, verifier)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Recomputes `sig0 = HMAC(root_key, identifier)` followed by the chain over
+/// `caveats`, independent of whether those caveats are actually satisfied.
+fn chain_signature(root_key: &[u8], identifier: &[u8], caveats: &[CaveatKind]) -> [u8; 32] {
+    let mut sig = hmac(root_key, identifier);
+    for caveat in caveats {
+        match caveat {
+            CaveatKind::FirstParty(predicate) => sig = hmac(&sig, predicate.as_bytes()),
+            CaveatKind::ThirdParty {
+                verification_key_id,
+                caveat_id,
+                ..
+            } => {
+                let mut data = verification_key_id.clone();
+                data.extend_from_slice(caveat_id);
+                sig = hmac(&sig, &data);
+            }
+        }
+    }
+    sig
+}
+
+/// Finds the discharge macaroon for a third-party caveat (matched by
+/// `caveat_id` against the discharge's `identifier`), checks its
+/// [`Macaroon::bind_discharge`] binding to `root_signature`, and checks its
+/// own first-party caveats against `verifier`. Discharges carrying their own
+/// third-party caveats aren't supported.
+fn verify_discharge(
+    root_signature: [u8; 32],
+    verification_key_id: &[u8],
+    caveat_id: &[u8],
+    discharges: &[Macaroon],
+    verifier: &impl Caveat,
+) -> Result<()> {
+    let discharge = discharges
+        .iter()
+        .find(|d| d.identifier == caveat_id)
+        .ok_or_else(|| AuthError::InvalidToken("missing discharge for third-party caveat".into()))?;
+
+    let unbound_sig = chain_signature(verification_key_id, &discharge.identifier, &discharge.caveats);
+    let mut data = root_signature.to_vec();
+    data.extend_from_slice(&unbound_sig);
+    let expected_bound_sig = hmac(&[0u8; 32], &data);
+    if !bool::from(expected_bound_sig.ct_eq(&discharge.signature)) {
+        return Err(AuthError::InvalidToken("discharge macaroon signature mismatch".into()));
+    }
+
+    for caveat in &discharge.caveats {
+        match caveat {
+            CaveatKind::FirstParty(predicate) => {
+                if !verifier.check(predicate) {
+                    return Err(AuthError::InvalidToken(format!(
+                        "caveat not satisfied: {predicate}"
+                    )));
+                }
+            }
+            CaveatKind::ThirdParty { .. } => {
+                return Err(AuthError::InvalidToken(
+                    "nested third-party caveats in a discharge macaroon are not supported".into(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysTrue;
+    impl Caveat for AlwaysTrue {
+        fn check(&self, _predicate: &str) -> bool {
+            true
+        }
+    }
+
+    struct DenyAll;
+    impl Caveat for DenyAll {
+        fn check(&self, _predicate: &str) -> bool {
+            false
+        }
+    }
+
+    fn confectionary() -> Confectionary {
+        let mut c = Confectionary::new();
+        c.add_key("k1", b"super-secret-root-key".to_vec());
+        c
+    }
+
+    #[test]
+    fn mint_and_verify_round_trip() {
+        let c = confectionary();
+        let macaroon = c.mint("k1", b"user-42".to_vec()).unwrap().with_caveat("scope = read");
+        assert!(c.verify(&macaroon, &[], &AlwaysTrue).is_ok());
+    }
+
+    #[test]
+    fn verification_fails_when_caveat_rejected() {
+        let c = confectionary();
+        let macaroon = c.mint("k1", b"user-42".to_vec()).unwrap().with_caveat("scope = read");
+        assert!(c.verify(&macaroon, &[], &DenyAll).is_err());
+    }
+
+    #[test]
+    fn tampered_caveat_invalidates_signature() {
+        let c = confectionary();
+        let macaroon = c.mint("k1", b"user-42".to_vec()).unwrap();
+        let mut tampered = macaroon.clone();
+        tampered.caveats.push(CaveatKind::FirstParty("scope = admin".into()));
+        assert!(c.verify(&tampered, &[], &AlwaysTrue).is_err());
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        let c = confectionary();
+        let macaroon = c
+            .mint("k1", b"user-42".to_vec())
+            .unwrap()
+            .with_caveat("scope = read")
+            .with_caveat("expires < 2030-01-01");
+        let token = macaroon.serialize();
+        let parsed = Macaroon::deserialize(&token).unwrap();
+        assert!(c.verify(&parsed, &[], &AlwaysTrue).is_ok());
+        assert_eq!(parsed.caveats.len(), 2);
+    }
+
+    #[test]
+    fn third_party_caveat_requires_matching_discharge() {
+        let c = confectionary();
+        let verification_key_id = b"shared-with-third-party".to_vec();
+        let caveat_id = b"discharge-id-1".to_vec();
+        let root = c
+            .mint("k1", b"user-42".to_vec())
+            .unwrap()
+            .with_third_party_caveat("https://third-party.example.com", verification_key_id.clone(), caveat_id.clone());
+
+        assert!(
+            c.verify(&root, &[], &AlwaysTrue).is_err(),
+            "should fail without a discharge"
+        );
+
+        let unbound_discharge = Macaroon {
+            identifier: caveat_id,
+            key_id: "unused".into(),
+            caveats: vec![],
+            signature: hmac(&verification_key_id, b"discharge-id-1"),
+        };
+        let discharge = root.bind_discharge(&unbound_discharge);
+        assert!(c.verify(&root, &[discharge], &AlwaysTrue).is_ok());
+    }
+
+    #[test]
+    fn third_party_caveat_rejects_wrong_discharge() {
+        let c = confectionary();
+        let verification_key_id = b"shared-with-third-party".to_vec();
+        let caveat_id = b"discharge-id-1".to_vec();
+        let root = c
+            .mint("k1", b"user-42".to_vec())
+            .unwrap()
+            .with_third_party_caveat("https://third-party.example.com", verification_key_id, caveat_id.clone());
+
+        let forged_discharge = Macaroon {
+            identifier: caveat_id,
+            key_id: "unused".into(),
+            caveats: vec![],
+            signature: hmac(b"wrong-key", b"discharge-id-1"),
+        };
+        assert!(c.verify(&root, &[forged_discharge], &AlwaysTrue).is_err());
+    }
+}
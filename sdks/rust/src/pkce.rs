@@ -0,0 +1,57 @@
+//! PKCE (RFC 7636) verifier/challenge generation for the Authorization Code flow.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// A freshly generated PKCE verifier and its derived S256 challenge.
+///
+/// Callers must persist `verifier` (keyed by `state`) between
+/// [`AuthVital::authorization_url`](crate::AuthVital::authorization_url) and
+/// [`AuthVital::exchange_code`](crate::AuthVital::exchange_code).
+#[derive(Clone)]
+pub struct Pkce {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+impl Pkce {
+    /// Generates a new random verifier (43-128 chars per RFC 7636) and its challenge.
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let verifier = URL_SAFE_NO_PAD.encode(bytes);
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        Self { verifier, challenge }
+    }
+
+    /// The `code_challenge_method` value sent in the authorization request.
+    pub const METHOD: &'static str = "S256";
+}
+
+/// Generates a random opaque `state` value for CSRF protection.
+pub fn generate_state() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenge_is_derived_from_verifier() {
+        let pkce = Pkce::generate();
+        let expected = URL_SAFE_NO_PAD.encode(Sha256::digest(pkce.verifier.as_bytes()));
+        assert_eq!(pkce.challenge, expected);
+    }
+
+    #[test]
+    fn verifiers_are_not_reused() {
+        let a = Pkce::generate();
+        let b = Pkce::generate();
+        assert_ne!(a.verifier, b.verifier);
+    }
+}
@@ -0,0 +1,21 @@
+//! A dedicated executor backing the [`blocking`](crate::blocking) client.
+//!
+//! Only compiled in with the `blocking` feature; the async client never uses this.
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start the blocking client's executor")
+    })
+}
+
+/// Drives `fut` to completion on the blocking client's dedicated runtime.
+pub(crate) fn block_on<F: Future>(fut: F) -> F::Output {
+    runtime().block_on(fut)
+}
@@ -0,0 +1,211 @@
+//! Encrypted-file fallback for platforms (or sandboxes) without a usable
+//! Secret Service portal.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::{TokenAttributes, TokenStore};
+use crate::error::{AuthError, Result};
+use crate::token::TokenSet;
+
+#[derive(Serialize, Deserialize, Default)]
+struct FileContents {
+    // Keyed by a stable string derived from `TokenAttributes` rather than the
+    // struct itself, so the on-disk format doesn't depend on field order.
+    entries: std::collections::HashMap<String, Vec<u8>>,
+}
+
+/// Writes `bytes` to `path`, creating the file with owner-only permissions
+/// from the start -- the contents are encrypted regardless, but there's no
+/// reason to let other local users even read the ciphertext, and chmod'ing
+/// after the fact would leave the file at the umask-derived default mode for
+/// a moment between creation and the chmod.
+#[cfg(unix)]
+fn write_owner_only(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut f| f.write_all(bytes))
+        .map_err(|e| AuthError::Config(e.to_string()))
+}
+
+#[cfg(not(unix))]
+fn write_owner_only(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    std::fs::write(path, bytes).map_err(|e| AuthError::Config(e.to_string()))
+}
+
+fn attrs_key(attrs: &TokenAttributes) -> String {
+    format!(
+        "{}\u{0}{}\u{0}{}",
+        attrs.issuer,
+        attrs.client_id,
+        attrs.subject.as_deref().unwrap_or("")
+    )
+}
+
+/// A [`TokenStore`] that persists an AES-256-GCM encrypted blob to a single file.
+///
+/// Used automatically by [`super::default_store`] when the `secret-service`
+/// feature is disabled or the process runs inside a Flatpak sandbox.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    cipher: Aes256Gcm,
+    // A single mutex guards read-modify-write of the whole file; contention
+    // is a non-issue since this is a local per-user credential store.
+    lock: Mutex<()>,
+}
+
+impl EncryptedFileStore {
+    /// `passphrase` should be a 32-byte key derived from the user's login
+    /// secret or an OS-protected seed; this type does not derive one for you.
+    pub fn new(path: PathBuf, passphrase: &[u8]) -> Result<Self> {
+        let cipher = Aes256Gcm::new_from_slice(passphrase)
+            .map_err(|_| AuthError::Config("passphrase must be exactly 32 bytes".into()))?;
+        Ok(Self {
+            path,
+            cipher,
+            lock: Mutex::new(()),
+        })
+    }
+
+    fn read_contents(&self) -> Result<FileContents> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(FileContents::default()),
+            Err(e) => return Err(AuthError::Config(e.to_string())),
+        };
+        if bytes.is_empty() {
+            return Ok(FileContents::default());
+        }
+        serde_json::from_slice(&bytes).map_err(|e| AuthError::Config(e.to_string()))
+    }
+
+    fn write_contents(&self, contents: &FileContents) -> Result<()> {
+        let bytes = serde_json::to_vec(contents).map_err(|e| AuthError::Config(e.to_string()))?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AuthError::Config(e.to_string()))?;
+        }
+        write_owner_only(&self.path, &bytes)
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut nonce_bytes = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+        let ciphertext = self
+            .cipher
+            .encrypt(nonce, plaintext)
+            .expect("encryption under a random 96-bit nonce cannot fail here");
+        let mut out = nonce_bytes.to_vec();
+        out.extend(ciphertext);
+        out
+    }
+
+    fn decrypt(&self, blob: &[u8]) -> Result<Vec<u8>> {
+        if blob.len() < 12 {
+            return Err(AuthError::InvalidToken("truncated ciphertext".into()));
+        }
+        let (nonce_bytes, ciphertext) = blob.split_at(12);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| AuthError::InvalidToken("failed to decrypt stored tokens".into()))
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenStore for EncryptedFileStore {
+    async fn put(&self, attrs: &TokenAttributes, tokens: &TokenSet) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut contents = self.read_contents()?;
+        let plaintext = serde_json::to_vec(tokens).map_err(|e| AuthError::Config(e.to_string()))?;
+        contents.entries.insert(attrs_key(attrs), self.encrypt(&plaintext));
+        self.write_contents(&contents)
+    }
+
+    async fn get(&self, attrs: &TokenAttributes) -> Result<Option<TokenSet>> {
+        let _guard = self.lock.lock().unwrap();
+        let contents = self.read_contents()?;
+        match contents.entries.get(&attrs_key(attrs)) {
+            Some(blob) => {
+                let plaintext = self.decrypt(blob)?;
+                Ok(Some(
+                    serde_json::from_slice(&plaintext).map_err(|e| AuthError::Config(e.to_string()))?,
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, attrs: &TokenAttributes) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut contents = self.read_contents()?;
+        contents.entries.remove(&attrs_key(attrs));
+        self.write_contents(&contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attrs() -> TokenAttributes {
+        TokenAttributes {
+            issuer: "https://id.example.com".into(),
+            client_id: "client-1".into(),
+            subject: Some("user-42".into()),
+        }
+    }
+
+    fn tokens() -> TokenSet {
+        TokenSet {
+            access_token: "at".into(),
+            refresh_token: Some("rt".into()),
+            id_token: None,
+            scope: None,
+            expires_at: std::time::SystemTime::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!("authvital-test-{}", std::process::id()));
+        let path = dir.join("tokens.json");
+        let store = EncryptedFileStore::new(path, &[7u8; 32]).unwrap();
+
+        store.put(&attrs(), &tokens()).await.unwrap();
+        let fetched = store.get(&attrs()).await.unwrap().unwrap();
+        assert_eq!(fetched.access_token, "at");
+
+        store.delete(&attrs()).await.unwrap();
+        assert!(store.get(&attrs()).await.unwrap().is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_is_created_with_owner_only_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("authvital-test-perms-{}", std::process::id()));
+        let path = dir.join("tokens.json");
+        let store = EncryptedFileStore::new(path.clone(), &[7u8; 32]).unwrap();
+
+        store.put(&attrs(), &tokens()).await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
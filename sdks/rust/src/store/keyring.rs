@@ -0,0 +1,141 @@
+//! [`TokenStore`](super::TokenStore) backed by the OS-native credential
+//! store, via the cross-platform [`keyring`] crate: the freedesktop Secret
+//! Service over D-Bus on Linux, Keychain on macOS, and Credential Manager on
+//! Windows. One dependency covers all three, so there's no `target_os`
+//! branching in this crate at all.
+
+use crate::error::{AuthError, Result};
+use crate::token::TokenSet;
+
+use super::TokenAttributes;
+
+/// Service name every AuthVital entry is stored under in the platform credential store.
+const SERVICE: &str = "authvital";
+
+/// A stable lookup key derived from [`TokenAttributes`], used as the platform
+/// credential store's "account"/"username" field.
+fn account(attrs: &TokenAttributes) -> String {
+    format!(
+        "{}\u{0}{}\u{0}{}",
+        attrs.issuer,
+        attrs.client_id,
+        attrs.subject.as_deref().unwrap_or("")
+    )
+}
+
+/// Gated behind the `secret-service` feature since it pulls in a platform
+/// credential-store dependency that isn't available in headless/CI
+/// environments without a D-Bus session (or equivalent) running.
+pub struct SecretServiceStore;
+
+impl SecretServiceStore {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SecretServiceStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn entry(attrs: &TokenAttributes) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, &account(attrs)).map_err(|e| AuthError::Config(e.to_string()))
+}
+
+#[async_trait::async_trait]
+impl super::TokenStore for SecretServiceStore {
+    async fn put(&self, attrs: &TokenAttributes, tokens: &TokenSet) -> Result<()> {
+        let secret = serde_json::to_string(tokens).map_err(|e| AuthError::Config(e.to_string()))?;
+        let attrs = attrs.clone();
+        tokio::task::spawn_blocking(move || entry(&attrs)?.set_password(&secret).map_err(keyring_err))
+            .await
+            .map_err(|e| AuthError::Config(e.to_string()))?
+    }
+
+    async fn get(&self, attrs: &TokenAttributes) -> Result<Option<TokenSet>> {
+        let attrs = attrs.clone();
+        let secret = tokio::task::spawn_blocking(move || match entry(&attrs)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keyring_err(e)),
+        })
+        .await
+        .map_err(|e| AuthError::Config(e.to_string()))??;
+
+        secret
+            .map(|s| serde_json::from_str(&s).map_err(|e| AuthError::Config(e.to_string())))
+            .transpose()
+    }
+
+    async fn delete(&self, attrs: &TokenAttributes) -> Result<()> {
+        let attrs = attrs.clone();
+        tokio::task::spawn_blocking(move || match entry(&attrs)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keyring_err(e)),
+        })
+        .await
+        .map_err(|e| AuthError::Config(e.to_string()))?
+    }
+}
+
+fn keyring_err(e: keyring::Error) -> AuthError {
+    AuthError::Config(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::TokenStore;
+
+    fn attrs() -> TokenAttributes {
+        TokenAttributes {
+            issuer: "https://id.example.com".into(),
+            client_id: "test-client".into(),
+            subject: Some(format!("keyring-test-{}", std::process::id())),
+        }
+    }
+
+    fn tokens() -> TokenSet {
+        TokenSet {
+            access_token: "at".into(),
+            refresh_token: Some("rt".into()),
+            id_token: None,
+            scope: None,
+            expires_at: std::time::SystemTime::now(),
+        }
+    }
+
+    /// Exercises the actual OS credential store this type is backed by, so a
+    /// build that silently resolves `keyring` to its platform-independent
+    /// `mock` backend (e.g. because no platform feature is enabled) fails
+    /// this test instead of looking identical to a real one: each call goes
+    /// through a freshly-constructed `Entry`, so the mock backend (which
+    /// doesn't persist across `Entry` instances) returns `None` from `get`
+    /// even right after a successful `put`.
+    ///
+    /// Skipped rather than failed when no credential store is reachable at
+    /// all, since headless CI and sandboxes commonly have neither a D-Bus
+    /// session nor a Secret Service provider running.
+    #[tokio::test]
+    async fn round_trips_through_the_real_credential_store() {
+        let store = SecretServiceStore::new();
+        let attrs = attrs();
+
+        if let Err(e) = store.put(&attrs, &tokens()).await {
+            eprintln!("skipping: no OS credential store reachable ({e})");
+            return;
+        }
+
+        let fetched = store.get(&attrs).await.unwrap();
+        assert_eq!(
+            fetched.map(|t| t.access_token),
+            Some("at".to_string()),
+            "put() succeeded but get() didn't return it back -- the store isn't actually persisting"
+        );
+
+        store.delete(&attrs).await.unwrap();
+        assert!(store.get(&attrs).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,66 @@
+//! Durable token persistence so CLI and desktop apps survive restarts without
+//! re-authenticating.
+//!
+//! [`TokenCache`](crate::token::TokenCache) keeps the current session's tokens
+//! in memory only; a [`TokenStore`] additionally persists them across process
+//! restarts, keyed by the issuer/client/subject they belong to.
+
+mod file;
+#[cfg(feature = "secret-service")]
+mod keyring;
+
+pub use file::EncryptedFileStore;
+#[cfg(feature = "secret-service")]
+pub use keyring::SecretServiceStore;
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::token::TokenSet;
+
+/// Identifies whose tokens are being stored, so a single store can hold
+/// sessions for several issuers/clients/subjects at once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenAttributes {
+    pub issuer: String,
+    pub client_id: String,
+    pub subject: Option<String>,
+}
+
+/// Persists token sets to durable storage, keyed by [`TokenAttributes`].
+///
+/// Implementations must treat the serialized [`TokenSet`] as a secret: it
+/// contains a live refresh token.
+#[async_trait]
+pub trait TokenStore: Send + Sync {
+    async fn put(&self, attrs: &TokenAttributes, tokens: &TokenSet) -> Result<()>;
+    async fn get(&self, attrs: &TokenAttributes) -> Result<Option<TokenSet>>;
+    async fn delete(&self, attrs: &TokenAttributes) -> Result<()>;
+}
+
+/// Picks a backend: the OS-native credential store when the `secret-service`
+/// feature is enabled and the process is not sandboxed, otherwise an
+/// encrypted on-disk file. Whether the credential store is actually reachable
+/// is not probed here; an unreachable one surfaces as errors from its methods. `passphrase` must be exactly 32 bytes; it's
+/// only used by the file-store fallback.
+///
+/// Sandboxed apps (detected via the presence of `/.flatpak-info`) always get
+/// the encrypted-file fallback, since Secret Service access inside a sandbox
+/// requires portal permissions this crate does not yet negotiate.
+pub fn default_store(
+    path: impl Into<std::path::PathBuf>,
+    passphrase: &[u8],
+) -> Result<Box<dyn TokenStore>> {
+    #[cfg(feature = "secret-service")]
+    {
+        if !is_sandboxed() {
+            return Ok(Box::new(SecretServiceStore::new()));
+        }
+    }
+    Ok(Box::new(EncryptedFileStore::new(path.into(), passphrase)?))
+}
+
+/// Whether the process is running inside a Flatpak sandbox.
+pub fn is_sandboxed() -> bool {
+    std::path::Path::new("/.flatpak-info").exists()
+}
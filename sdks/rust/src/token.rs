@@ -0,0 +1,93 @@
+//! Token set representation and the in-memory, auto-refreshing token cache.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// Tokens issued for a single authenticated session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSet {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub id_token: Option<String>,
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Wall-clock expiry of `access_token`, derived from the response's `expires_in`.
+    #[serde(with = "expires_at_as_unix_secs", default = "default_expiry")]
+    pub expires_at: SystemTime,
+}
+
+fn default_expiry() -> SystemTime {
+    SystemTime::now()
+}
+
+/// (De)serializes `expires_at` as whole seconds since the Unix epoch, so
+/// persisted token sets (e.g. in [`crate::store::TokenStore`]) keep their expiry.
+mod expires_at_as_unix_secs {
+    use super::{Duration, SystemTime, UNIX_EPOCH};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        let secs = value
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs();
+        secs.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        let secs = u64::deserialize(deserializer)?;
+        Ok(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+impl TokenSet {
+    /// Populates `expires_at` from a provider's `expires_in` (seconds) field.
+    pub fn with_expires_in(mut self, expires_in: u64) -> Self {
+        self.expires_at = SystemTime::now() + Duration::from_secs(expires_in);
+        self
+    }
+
+    /// Whether `access_token` is expired or will expire within `skew`.
+    pub fn needs_refresh(&self, skew: Duration) -> bool {
+        match self.expires_at.checked_sub(skew) {
+            Some(refresh_at) => SystemTime::now() >= refresh_at,
+            None => true,
+        }
+    }
+}
+
+/// Caches the current [`TokenSet`] for a client and decides when it must be refreshed.
+///
+/// This is purely an in-memory, single-session cache; see [`crate::store::TokenStore`]
+/// for durable, cross-process persistence.
+pub struct TokenCache {
+    skew: Duration,
+    current: RwLock<Option<TokenSet>>,
+}
+
+impl TokenCache {
+    pub fn new(skew: Duration) -> Self {
+        Self {
+            skew,
+            current: RwLock::new(None),
+        }
+    }
+
+    pub async fn set(&self, tokens: TokenSet) {
+        *self.current.write().await = Some(tokens);
+    }
+
+    pub async fn get(&self) -> Option<TokenSet> {
+        self.current.read().await.clone()
+    }
+
+    /// Returns `Some(tokens)` only if no refresh is currently needed.
+    pub async fn get_fresh(&self) -> Option<TokenSet> {
+        let guard = self.current.read().await;
+        match guard.as_ref() {
+            Some(tokens) if !tokens.needs_refresh(self.skew) => Some(tokens.clone()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! The HTTP seam between [`AuthVital`](crate::AuthVital) and the wire.
+//!
+//! Downstream crates that want to unit-test code built on `AuthVital` without
+//! a live identity server can implement [`AuthVitalTransport`] themselves and
+//! construct a client via a test-only injection point, stubbing token
+//! exchange and userinfo responses directly.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::discovery::Jwks;
+use crate::error::{AuthError, Result};
+
+/// A token endpoint response, shared by the authorization-code exchange and
+/// refresh-token grants.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub id_token: Option<String>,
+    pub scope: Option<String>,
+    pub expires_in: u64,
+}
+
+/// Everything `AuthVital` needs from an HTTP client, kept narrow enough to stub
+/// in tests: a plain JSON `GET` (discovery, userinfo), a JWKS fetch, and a
+/// form-encoded token-endpoint `POST`.
+#[async_trait]
+pub trait AuthVitalTransport: Send + Sync {
+    /// Fetches and JSON-decodes `url`, optionally as a bearer-authenticated request.
+    /// Used for the discovery document (no token) and userinfo endpoint (bearer token).
+    async fn get(&self, url: &str, bearer_token: Option<&str>) -> Result<serde_json::Value>;
+
+    /// Fetches and decodes a JWK Set from `url`.
+    async fn get_jwks(&self, url: &str) -> Result<Jwks>;
+
+    /// Submits a form-encoded request to a token endpoint and decodes the response.
+    async fn post_token(&self, url: &str, params: &HashMap<&str, &str>) -> Result<TokenResponse>;
+}
+
+/// The real [`AuthVitalTransport`], backed by [`reqwest`].
+pub struct HttpTransport {
+    http: reqwest::Client,
+}
+
+impl HttpTransport {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+impl Default for HttpTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn check_status(resp: reqwest::Response) -> Result<reqwest::Response> {
+    if resp.status().is_success() {
+        Ok(resp)
+    } else {
+        let status = resp.status().as_u16();
+        let body = resp.text().await.unwrap_or_default();
+        Err(AuthError::Provider { status, body })
+    }
+}
+
+#[async_trait]
+impl AuthVitalTransport for HttpTransport {
+    async fn get(&self, url: &str, bearer_token: Option<&str>) -> Result<serde_json::Value> {
+        let mut req = self.http.get(url);
+        if let Some(token) = bearer_token {
+            req = req.bearer_auth(token);
+        }
+        let resp = req.send().await.map_err(|e| AuthError::Transport(e.to_string()))?;
+        check_status(resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AuthError::Transport(e.to_string()))
+    }
+
+    async fn get_jwks(&self, url: &str) -> Result<Jwks> {
+        let resp = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AuthError::Transport(e.to_string()))?;
+        check_status(resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AuthError::Transport(e.to_string()))
+    }
+
+    async fn post_token(&self, url: &str, params: &HashMap<&str, &str>) -> Result<TokenResponse> {
+        let resp = self
+            .http
+            .post(url)
+            .form(params)
+            .send()
+            .await
+            .map_err(|e| AuthError::Transport(e.to_string()))?;
+        check_status(resp)
+            .await?
+            .json()
+            .await
+            .map_err(|e| AuthError::Transport(e.to_string()))
+    }
+}